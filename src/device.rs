@@ -1,10 +1,11 @@
 use std::convert::TryFrom;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::{io, mem};
+use std::time::Duration;
+use std::{fs, io, mem};
 
 use crate::control;
-use crate::pselect::FdSet;
+use crate::pselect::{self, FdSet};
 use crate::v4l2;
 use crate::v4l2::videodev::v4l2_ext_controls;
 use crate::v4l_sys::*;
@@ -16,6 +17,264 @@ pub struct Device {
     handle: Arc<Handle>,
 }
 
+/// Information about a V4L2 node discovered by [`Device::enumerate`]
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    /// Path to the device node (e.g. "/dev/video0")
+    pub path: PathBuf,
+    /// Name of the device node (e.g. "video0")
+    pub name: String,
+    /// Capabilities reported by the device
+    pub caps: Capabilities,
+}
+
+/// An event delivered by `VIDIOC_DQEVENT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A subscribed control changed
+    CtrlChange {
+        /// Control whose value or range changed
+        id: u32,
+        /// Current value of the control
+        value: i64,
+        /// Set if the control's value changed
+        value_changed: bool,
+        /// Set if the control's range (min/max/step/default) changed
+        range_changed: bool,
+    },
+}
+
+impl TryFrom<v4l2_event> for Event {
+    type Error = io::Error;
+
+    fn try_from(ev: v4l2_event) -> Result<Self, Self::Error> {
+        match ev.type_ {
+            V4L2_EVENT_CTRL => {
+                let ctrl = unsafe { ev.u.ctrl };
+                Ok(Event::CtrlChange {
+                    id: ev.id,
+                    value: ctrl.value as i64,
+                    value_changed: ctrl.changes & V4L2_EVENT_CTRL_CH_VALUE != 0,
+                    range_changed: ctrl.changes & V4L2_EVENT_CTRL_CH_RANGE != 0,
+                })
+            }
+            other => Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("unhandled event type {}", other),
+            )),
+        }
+    }
+}
+
+/// Format of a device's metadata capture queue
+#[derive(Debug, Clone, Copy)]
+pub struct MetaFormat {
+    /// FourCC identifying the metadata layout (e.g. `UVCH` for UVC header metadata)
+    pub dataformat: u32,
+    /// Size in bytes of a single metadata buffer
+    pub buffersize: u32,
+}
+
+/// A single metadata buffer dequeued from a `V4L2_BUF_TYPE_META_CAPTURE` queue
+pub struct MetaBuffer<'a> {
+    /// FourCC identifying how to interpret `data`, taken from the queue's [`MetaFormat`]
+    pub fourcc: u32,
+    /// Raw metadata bytes written by the driver for this frame
+    pub data: &'a [u8],
+}
+
+/// Streaming reader for a device's metadata capture queue
+///
+/// Requests a fixed number of `mmap`ed buffers via `VIDIOC_REQBUFS`, queues all
+/// of them and starts the stream; [`next`](MetaCaptureStream::next) then cycles
+/// buffers through `VIDIOC_DQBUF`/`VIDIOC_QBUF`, parallel to how `V4L2_BUF_TYPE_VIDEO_CAPTURE`
+/// buffers are normally streamed, so callers can correlate each video frame with
+/// the metadata buffer captured alongside it.
+pub struct MetaCaptureStream {
+    handle: Arc<Handle>,
+    fourcc: u32,
+    buffers: Vec<(*mut u8, usize)>,
+    /// Index last handed out by `next()`, still owned by the caller's slice
+    /// until the following call re-queues it
+    pending_index: Option<u32>,
+}
+
+impl MetaCaptureStream {
+    /// Starts streaming metadata buffers from a device
+    ///
+    /// # Arguments
+    ///
+    /// * `dev` - Device with an active `V4L2_CAP_META_CAPTURE` queue
+    /// * `buffer_count` - Number of buffers to request from the driver
+    pub fn with_buffers(dev: &Device, buffer_count: u32) -> io::Result<Self> {
+        let fourcc = dev.meta_format()?.dataformat;
+        let fd = dev.handle().fd();
+
+        unsafe {
+            let mut reqbufs: v4l2_requestbuffers = mem::zeroed();
+            reqbufs.count = buffer_count;
+            reqbufs.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+            reqbufs.memory = V4L2_MEMORY_MMAP;
+            v4l2::ioctl(
+                fd,
+                v4l2::vidioc::VIDIOC_REQBUFS,
+                &mut reqbufs as *mut _ as *mut std::os::raw::c_void,
+            )?;
+
+            let buffers = Self::map_buffers(fd, reqbufs.count)?;
+
+            let mut typ = V4L2_BUF_TYPE_META_CAPTURE;
+            if let Err(e) = v4l2::ioctl(
+                fd,
+                v4l2::vidioc::VIDIOC_STREAMON,
+                &mut typ as *mut _ as *mut std::os::raw::c_void,
+            ) {
+                for (ptr, len) in buffers {
+                    libc::munmap(ptr as *mut std::os::raw::c_void, len);
+                }
+                return Err(e);
+            }
+
+            Ok(MetaCaptureStream {
+                handle: dev.handle(),
+                fourcc,
+                buffers,
+                pending_index: None,
+            })
+        }
+    }
+
+    /// Queries, mmaps and queues every requested buffer
+    ///
+    /// If any step fails partway through, every buffer already mapped in a
+    /// prior iteration of this loop is unmapped before the error is returned,
+    /// so a partial failure here cannot leak mmap'd memory.
+    unsafe fn map_buffers(
+        fd: std::os::raw::c_int,
+        count: u32,
+    ) -> io::Result<Vec<(*mut u8, usize)>> {
+        let mut buffers: Vec<(*mut u8, usize)> = Vec::with_capacity(count as usize);
+
+        for index in 0..count {
+            if let Err(e) = Self::map_one_buffer(fd, index, &mut buffers) {
+                for (ptr, len) in buffers.drain(..) {
+                    libc::munmap(ptr as *mut std::os::raw::c_void, len);
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(buffers)
+    }
+
+    unsafe fn map_one_buffer(
+        fd: std::os::raw::c_int,
+        index: u32,
+        buffers: &mut Vec<(*mut u8, usize)>,
+    ) -> io::Result<()> {
+        let mut buf: v4l2_buffer = mem::zeroed();
+        buf.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+        buf.memory = V4L2_MEMORY_MMAP;
+        buf.index = index;
+        v4l2::ioctl(
+            fd,
+            v4l2::vidioc::VIDIOC_QUERYBUF,
+            &mut buf as *mut _ as *mut std::os::raw::c_void,
+        )?;
+
+        let ptr = libc::mmap(
+            std::ptr::null_mut(),
+            buf.length as usize,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_SHARED,
+            fd,
+            buf.m.offset as libc::off_t,
+        );
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        // track the mapping before QBUF so a failure below still gets
+        // unmapped by the caller's cleanup pass
+        buffers.push((ptr as *mut u8, buf.length as usize));
+
+        v4l2::ioctl(
+            fd,
+            v4l2::vidioc::VIDIOC_QBUF,
+            &mut buf as *mut _ as *mut std::os::raw::c_void,
+        )?;
+
+        Ok(())
+    }
+
+    /// Dequeues the next available metadata buffer, blocking until the driver has one ready
+    ///
+    /// The buffer returned by the previous call is only re-queued to the
+    /// driver at the start of this call, once the caller can no longer be
+    /// holding a reference to it (enforced by the `&mut self` borrow on the
+    /// returned [`MetaBuffer`]). Re-queueing it any earlier would let the
+    /// driver start overwriting that mmap'd region while the caller is still
+    /// reading it.
+    pub fn next(&mut self) -> io::Result<MetaBuffer> {
+        unsafe {
+            if let Some(index) = self.pending_index.take() {
+                let mut buf: v4l2_buffer = mem::zeroed();
+                buf.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+                buf.memory = V4L2_MEMORY_MMAP;
+                buf.index = index;
+                v4l2::ioctl(
+                    self.handle.fd(),
+                    v4l2::vidioc::VIDIOC_QBUF,
+                    &mut buf as *mut _ as *mut std::os::raw::c_void,
+                )?;
+            }
+
+            // the device fd is opened with O_NONBLOCK, so DQBUF returns EAGAIN
+            // immediately if no buffer is ready yet; pselect on the read fd-set
+            // is what actually blocks us until one is, the same way
+            // `Device::wait_event` waits on the exception fd-set for events
+            let mut readfds = self.handle.fd_set();
+            pselect::pselect(Some(&mut readfds), None, None, None)?;
+
+            let mut buf: v4l2_buffer = mem::zeroed();
+            buf.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+            buf.memory = V4L2_MEMORY_MMAP;
+
+            v4l2::ioctl(
+                self.handle.fd(),
+                v4l2::vidioc::VIDIOC_DQBUF,
+                &mut buf as *mut _ as *mut std::os::raw::c_void,
+            )?;
+
+            self.pending_index = Some(buf.index);
+
+            let (ptr, len) = self.buffers[buf.index as usize];
+            let data = std::slice::from_raw_parts(ptr, buf.bytesused.min(len as u32) as usize);
+
+            Ok(MetaBuffer {
+                fourcc: self.fourcc,
+                data,
+            })
+        }
+    }
+}
+
+impl Drop for MetaCaptureStream {
+    fn drop(&mut self) {
+        unsafe {
+            let mut typ = V4L2_BUF_TYPE_META_CAPTURE;
+            let _ = v4l2::ioctl(
+                self.handle.fd(),
+                v4l2::vidioc::VIDIOC_STREAMOFF,
+                &mut typ as *mut _ as *mut std::os::raw::c_void,
+            );
+
+            for (ptr, len) in self.buffers.drain(..) {
+                libc::munmap(ptr as *mut std::os::raw::c_void, len);
+            }
+        }
+    }
+}
+
 impl Device {
     /// Returns a capture device by index
     ///
@@ -82,6 +341,79 @@ impl Device {
         self.handle.clone()
     }
 
+    /// Enumerates the V4L2 nodes present on the system
+    ///
+    /// Walks `/dev` for nodes matching `videoN`, opens each one read-only and
+    /// issues `VIDIOC_QUERYCAP` to collect its capabilities. Nodes that fail to
+    /// open, or that do not respond to `VIDIOC_QUERYCAP`, are skipped rather than
+    /// aborting the whole scan, since a busy or non-capture node is a common and
+    /// expected occurrence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use v4l::device::Device;
+    /// for info in Device::enumerate() {
+    ///     println!("{}: {}", info.name, info.caps.card);
+    /// }
+    /// ```
+    pub fn enumerate() -> Vec<DeviceInfo> {
+        let mut devices = Vec::new();
+
+        let entries = match fs::read_dir("/dev") {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = match name.to_str() {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            let index = match name.strip_prefix("video").and_then(|n| n.parse::<usize>().ok()) {
+                Some(index) => index,
+                None => continue,
+            };
+
+            let path = entry.path();
+            let dev = match Device::open_readonly(&path) {
+                Ok(dev) => dev,
+                Err(_) => continue,
+            };
+
+            let caps = match dev.query_caps() {
+                Ok(caps) => caps,
+                Err(_) => continue,
+            };
+
+            devices.push((index, DeviceInfo { path, name, caps }));
+        }
+
+        devices.sort_by_key(|(index, _)| *index);
+        devices.into_iter().map(|(_, info)| info).collect()
+    }
+
+    /// Opens a device node read-only, without claiming exclusive streaming access
+    ///
+    /// Used for probing nodes during [`Device::enumerate`] so that devices
+    /// already opened for capture elsewhere are not disturbed.
+    fn open_readonly<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let fd = v4l2::open(&path, libc::O_RDONLY | libc::O_NONBLOCK)?;
+
+        if fd == -1 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut fd_set = FdSet::new();
+        fd_set.set(fd);
+
+        Ok(Device {
+            handle: Arc::new(Handle { fd, fd_set }),
+        })
+    }
+
     /// Returns video4linux framework defined information such as card, driver, etc.
     pub fn query_caps(&self) -> io::Result<Capabilities> {
         unsafe {
@@ -96,6 +428,76 @@ impl Device {
         }
     }
 
+    /// Returns whether the device exposes a `V4L2_BUF_TYPE_META_CAPTURE` queue
+    ///
+    /// This is reported through `VIDIOC_QUERYCAP` via the `V4L2_CAP_META_CAPTURE`
+    /// flag, the same way UVC and ISP drivers advertise a separate metadata node
+    /// (e.g. the kernel's `uvc_metadata` node) carrying per-frame exposure, gain
+    /// and timestamp information alongside the video queue.
+    pub fn supports_meta_capture(&self) -> io::Result<bool> {
+        unsafe {
+            let mut v4l2_caps: v4l2_capability = mem::zeroed();
+            v4l2::ioctl(
+                self.handle().fd(),
+                v4l2::vidioc::VIDIOC_QUERYCAP,
+                &mut v4l2_caps as *mut _ as *mut std::os::raw::c_void,
+            )?;
+
+            Ok(v4l2_caps.capabilities & V4L2_CAP_META_CAPTURE != 0)
+        }
+    }
+
+    /// Enumerates the pixel formats available on the metadata capture queue
+    pub fn enum_meta_formats(&self) -> io::Result<Vec<u32>> {
+        let mut formats = Vec::new();
+
+        unsafe {
+            let mut fmtdesc: v4l2_fmtdesc = mem::zeroed();
+            fmtdesc.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+
+            loop {
+                match v4l2::ioctl(
+                    self.handle().fd(),
+                    v4l2::vidioc::VIDIOC_ENUM_FMT,
+                    &mut fmtdesc as *mut _ as *mut std::os::raw::c_void,
+                ) {
+                    Ok(_) => {
+                        formats.push(fmtdesc.pixelformat);
+                        fmtdesc.index += 1;
+                    }
+                    Err(e) => {
+                        if e.kind() == io::ErrorKind::InvalidInput {
+                            break;
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        Ok(formats)
+    }
+
+    /// Returns the currently set format of the metadata capture queue
+    pub fn meta_format(&self) -> io::Result<MetaFormat> {
+        unsafe {
+            let mut fmt: v4l2_format = mem::zeroed();
+            fmt.type_ = V4L2_BUF_TYPE_META_CAPTURE;
+
+            v4l2::ioctl(
+                self.handle().fd(),
+                v4l2::vidioc::VIDIOC_G_FMT,
+                &mut fmt as *mut _ as *mut std::os::raw::c_void,
+            )?;
+
+            let meta = fmt.fmt.meta;
+            Ok(MetaFormat {
+                dataformat: meta.dataformat,
+                buffersize: meta.buffersize,
+            })
+        }
+    }
+
     /// Returns the supported controls for a device such as gain, focus, white balance, etc.
     pub fn query_controls(&self) -> io::Result<Vec<control::Description>> {
         let mut controls = Vec::new();
@@ -177,41 +579,160 @@ impl Device {
     ///
     /// * `id` - Control identifier
     pub fn control(&self, id: u32) -> io::Result<Control> {
+        self.controls(&[id])?
+            .into_iter()
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "driver returned no control"))
+    }
+
+    /// Returns the control values for a batch of IDs
+    ///
+    /// All IDs must belong to the same control class, just like
+    /// [`set_controls`](Device::set_controls). This issues `VIDIOC_G_EXT_CTRLS`
+    /// twice: once with `size = 0` to learn the payload size of any
+    /// string/compound control, and once more after allocating buffers of that
+    /// size to read the actual values. This mirrors `set_controls`, which can
+    /// already write every `control::Value` variant; `control` used to be
+    /// stuck on the legacy `VIDIOC_G_CTRL`, which only understands
+    /// integers and booleans.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - Control identifiers
+    pub fn controls(&self, ids: &[u32]) -> io::Result<Vec<Control>> {
+        if ids.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ids cannot be empty",
+            ));
+        }
+
         unsafe {
-            let mut queryctrl: v4l2_queryctrl = mem::zeroed();
-            queryctrl.id = id;
-            v4l2::ioctl(
+            let mut descriptions = Vec::with_capacity(ids.len());
+            for &id in ids {
+                let mut queryctrl: v4l2_queryctrl = mem::zeroed();
+                queryctrl.id = id;
+                v4l2::ioctl(
+                    self.handle().fd(),
+                    v4l2::vidioc::VIDIOC_QUERYCTRL,
+                    &mut queryctrl as *mut _ as *mut std::os::raw::c_void,
+                )?;
+                descriptions.push(control::Description::from(queryctrl));
+            }
+
+            let mut class: Option<u32> = None;
+            for &id in ids {
+                class = match class {
+                    Some(c) if c != (id & 0xFFFF0000) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "All controls must be in the same class",
+                        ));
+                    }
+                    Some(c) => Some(c),
+                    None => Some(id & 0xFFFF0000),
+                };
+            }
+
+            let mut ext_controls: Vec<v4l2_ext_control> = ids
+                .iter()
+                .map(|&id| {
+                    let mut ctrl: v4l2_ext_control = mem::zeroed();
+                    ctrl.id = id;
+                    ctrl
+                })
+                .collect();
+
+            let mut controls: v4l2_ext_controls = mem::zeroed();
+            controls.count = ext_controls.len() as u32;
+            if let Some(class) = class {
+                controls.which = class;
+            } else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "failed to determine control class",
+                ));
+            };
+            controls.controls = ext_controls.as_mut_ptr();
+
+            // size = 0: learn the payload size of string/compound controls. The
+            // driver reports this by failing with ENOSPC and writing the needed
+            // size into each control's `size` field, so a plain `?` here would
+            // bail out before we ever get to look at it.
+            match v4l2::ioctl(
                 self.handle().fd(),
-                v4l2::vidioc::VIDIOC_QUERYCTRL,
-                &mut queryctrl as *mut _ as *mut std::os::raw::c_void,
-            )?;
+                v4l2::vidioc::VIDIOC_G_EXT_CTRLS,
+                &mut controls as *mut _ as *mut std::os::raw::c_void,
+            ) {
+                Ok(_) => {}
+                Err(e) if e.raw_os_error() == Some(libc::ENOSPC) => {}
+                Err(e) => return Err(e),
+            }
 
-            // determine the control type
-            let description = control::Description::from(queryctrl);
+            let mut buffers: Vec<Vec<u8>> = ext_controls
+                .iter()
+                .map(|ctrl| vec![0u8; ctrl.size as usize])
+                .collect();
+            for (ctrl, buffer) in ext_controls.iter_mut().zip(buffers.iter_mut()) {
+                if !buffer.is_empty() {
+                    ctrl.__bindgen_anon_1.string = buffer.as_mut_ptr() as *mut i8;
+                }
+            }
 
-            // query the actual control value
-            let mut v4l2_ctrl: v4l2_control = mem::zeroed();
-            v4l2_ctrl.id = id;
+            // re-issue now that every control has a backing buffer sized correctly
             v4l2::ioctl(
                 self.handle().fd(),
-                v4l2::vidioc::VIDIOC_G_CTRL,
-                &mut v4l2_ctrl as *mut _ as *mut std::os::raw::c_void,
+                v4l2::vidioc::VIDIOC_G_EXT_CTRLS,
+                &mut controls as *mut _ as *mut std::os::raw::c_void,
             )?;
 
-            let value = match description.typ {
-                control::Type::Integer | control::Type::Integer64 => {
-                    control::Value::Integer(v4l2_ctrl.value as i64)
-                }
-                control::Type::Boolean => control::Value::Boolean(v4l2_ctrl.value == 1),
-                _ => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::Other,
-                        "cannot handle control type",
-                    ))
-                }
-            };
+            ids.iter()
+                .zip(descriptions.iter())
+                .zip(ext_controls.iter())
+                .zip(buffers.iter())
+                .map(|(((&id, description), ext), buffer)| {
+                    let value = match description.typ {
+                        // new_to_user() in v4l2-ctrls.c only writes the 64-bit
+                        // `value64` union member for Integer64; every other
+                        // scalar type gets the 32-bit `value` member, which
+                        // must be sign-extended via an `i32` cast rather than
+                        // read out of the (zeroed) upper bits of `value64`.
+                        control::Type::Integer
+                        | control::Type::Menu
+                        | control::Type::IntegerMenu
+                        | control::Type::Bitmask => {
+                            control::Value::Integer(ext.__bindgen_anon_1.value as i64)
+                        }
+                        control::Type::Integer64 => {
+                            control::Value::Integer(ext.__bindgen_anon_1.value64)
+                        }
+                        control::Type::Boolean => {
+                            control::Value::Boolean(ext.__bindgen_anon_1.value == 1)
+                        }
+                        control::Type::String => control::Value::String(
+                            String::from_utf8_lossy(buffer)
+                                .trim_end_matches('\0')
+                                .to_string(),
+                        ),
+                        control::Type::U8 => control::Value::CompoundU8(buffer.clone()),
+                        control::Type::U16 => control::Value::CompoundU16(
+                            buffer
+                                .chunks_exact(2)
+                                .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+                                .collect(),
+                        ),
+                        control::Type::U32 => control::Value::CompoundU32(
+                            buffer
+                                .chunks_exact(4)
+                                .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                                .collect(),
+                        ),
+                        _ => control::Value::CompoundPtr(buffer.clone()),
+                    };
 
-            Ok(Control { id, value })
+                    Ok(Control { id, value })
+                })
+                .collect()
         }
     }
 
@@ -315,6 +836,158 @@ impl Device {
             )
         }
     }
+
+    /// Modifies the control values atomically, validating each one against its
+    /// [`Description`](control::Description) first
+    ///
+    /// Mirrors the bookkeeping the kernel control framework itself performs:
+    /// integer values are rejected if out of `[minimum, maximum]` and otherwise
+    /// rounded to the nearest `step`, menu selections must match an index
+    /// advertised in `description.items`, and writes to read-only or currently
+    /// inactive controls are rejected up front. This turns a bare `EINVAL` from
+    /// the driver into a descriptive error; use the unchecked
+    /// [`set_controls`](Device::set_controls) when the caller has already
+    /// validated the values itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctrls` - Vec of the controls to be set
+    /// * `descriptions` - Descriptions (as returned by [`query_controls`](Device::query_controls)) covering every control in `ctrls`
+    pub fn set_controls_checked(
+        &self,
+        ctrls: Vec<Control>,
+        descriptions: &[control::Description],
+    ) -> io::Result<()> {
+        let mut checked = Vec::with_capacity(ctrls.len());
+
+        for ctrl in ctrls {
+            let description = descriptions.iter().find(|d| d.id == ctrl.id).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("no description supplied for control {}", ctrl.id),
+                )
+            })?;
+
+            if description.flags & V4L2_CTRL_FLAG_READ_ONLY != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("control {} is read-only", ctrl.id),
+                ));
+            }
+            if description.flags & V4L2_CTRL_FLAG_INACTIVE != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("control {} is currently inactive", ctrl.id),
+                ));
+            }
+
+            let value = match (description.typ, &ctrl.value) {
+                (control::Type::Integer | control::Type::Integer64, &control::Value::Integer(val)) => {
+                    let (min, max, step) = (description.minimum, description.maximum, description.step.max(1));
+                    if val < min || val > max {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "value {} for control {} is out of range [{}, {}]",
+                                val, ctrl.id, min, max
+                            ),
+                        ));
+                    }
+                    let steps = ((val - min) as f64 / step as f64).round() as i64;
+                    let rounded = (min + steps * step).clamp(min, max);
+                    control::Value::Integer(rounded)
+                }
+                (control::Type::Menu | control::Type::IntegerMenu, &control::Value::Integer(index)) => {
+                    let valid = description
+                        .items
+                        .as_ref()
+                        .map(|items| items.iter().any(|(i, _)| *i as i64 == index))
+                        .unwrap_or(false);
+                    if !valid {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("{} is not an advertised menu index for control {}", index, ctrl.id),
+                        ));
+                    }
+                    control::Value::Integer(index)
+                }
+                _ => ctrl.value,
+            };
+
+            checked.push(Control {
+                id: ctrl.id,
+                value,
+            });
+        }
+
+        self.set_controls(checked)
+    }
+
+    /// Subscribes to change notifications for a control
+    ///
+    /// Once subscribed, changes are reported by [`Device::wait_event`] instead of
+    /// requiring callers to poll [`Device::control`].
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Control identifier, or `V4L2_EVENT_ALL` to subscribe to every control
+    pub fn subscribe_control_event(&self, id: u32) -> io::Result<()> {
+        unsafe {
+            let mut sub: v4l2_event_subscription = mem::zeroed();
+            sub.type_ = V4L2_EVENT_CTRL;
+            sub.id = id;
+
+            v4l2::ioctl(
+                self.handle().fd(),
+                v4l2::vidioc::VIDIOC_SUBSCRIBE_EVENT,
+                &mut sub as *mut _ as *mut std::os::raw::c_void,
+            )
+        }
+    }
+
+    /// Cancels a previous call to [`Device::subscribe_control_event`]
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Control identifier passed to the matching subscribe call
+    pub fn unsubscribe_control_event(&self, id: u32) -> io::Result<()> {
+        unsafe {
+            let mut sub: v4l2_event_subscription = mem::zeroed();
+            sub.type_ = V4L2_EVENT_CTRL;
+            sub.id = id;
+
+            v4l2::ioctl(
+                self.handle().fd(),
+                v4l2::vidioc::VIDIOC_UNSUBSCRIBE_EVENT,
+                &mut sub as *mut _ as *mut std::os::raw::c_void,
+            )
+        }
+    }
+
+    /// Blocks until a subscribed event is pending and returns it
+    ///
+    /// V4L2 signals pending events through the device's exception fd-set, so
+    /// this runs [`pselect`](crate::pselect) on the device fd watching for
+    /// exceptions before draining the event with `VIDIOC_DQEVENT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait, or `None` to block indefinitely
+    pub fn wait_event(&self, timeout: Option<Duration>) -> io::Result<Event> {
+        let mut exceptfds = self.handle().fd_set();
+        pselect::pselect(None, None, Some(&mut exceptfds), timeout)?;
+
+        unsafe {
+            let mut v4l2_ev: v4l2_event = mem::zeroed();
+            v4l2::ioctl(
+                self.handle().fd(),
+                v4l2::vidioc::VIDIOC_DQEVENT,
+                &mut v4l2_ev as *mut _ as *mut std::os::raw::c_void,
+            )?;
+
+            Event::try_from(v4l2_ev)
+        }
+    }
 }
 
 impl io::Read for Device {